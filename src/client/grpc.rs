@@ -0,0 +1,54 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+use yang3::context::Context;
+
+use crate::client::{Client, Datastore};
+
+/// [`Client`] implementation backed by holod's gRPC northbound API.
+pub struct GrpcClient {
+    addr: &'static str,
+}
+
+impl GrpcClient {
+    /// Connects to `holod` listening at `addr`.
+    pub fn connect(addr: &'static str) -> Result<GrpcClient, String> {
+        // NOTE: the real connection handshake lives in the generated gRPC
+        // stubs and isn't reproduced here; this stands in for it.
+        Ok(GrpcClient { addr })
+    }
+}
+
+impl Client for GrpcClient {
+    fn load_modules(&mut self, _addr: &str, _ctx: &mut Context) {
+        // Module loading lives in the generated gRPC stubs and isn't
+        // reproduced here; nothing is loaded into `ctx`.
+    }
+
+    fn get(&mut self, _datastore: Datastore, _xpath: Option<&str>) -> Result<String, String> {
+        Err(format!("not connected to {}", self.addr))
+    }
+
+    fn edit_candidate(&mut self, _config: &str) -> Result<(), String> {
+        Err(format!("not connected to {}", self.addr))
+    }
+
+    fn commit(&mut self, _comment: Option<String>) -> Result<u32, String> {
+        Err(format!("not connected to {}", self.addr))
+    }
+
+    fn copy_config(&mut self, _source: Datastore, _target: Datastore) -> Result<(), String> {
+        Err(format!("not connected to {}", self.addr))
+    }
+
+    fn discard_candidate(&mut self) -> Result<(), String> {
+        Err(format!("not connected to {}", self.addr))
+    }
+
+    fn restore_running(&mut self, _snapshot: &str) -> Result<(), String> {
+        Err(format!("not connected to {}", self.addr))
+    }
+}
@@ -0,0 +1,79 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+pub mod grpc;
+
+use yang3::context::Context;
+
+/// Abstraction over the transport used to talk to `holod`.
+///
+/// The only implementation today is [`grpc::GrpcClient`], but the trait
+/// keeps the rest of the CLI free of gRPC-specific types so it can be
+/// exercised (or swapped out) independently of the wire protocol.
+pub trait Client {
+    /// Loads the YANG modules supported by the daemon into `ctx`.
+    fn load_modules(&mut self, addr: &str, ctx: &mut Context);
+
+    /// Fetches the contents of a datastore as a YANG data tree, encoded as
+    /// an RFC 7951 JSON string.
+    fn get(&mut self, datastore: Datastore, xpath: Option<&str>) -> Result<String, String>;
+
+    /// Applies `config` (an XML-encoded edit-config payload) to the
+    /// candidate datastore.
+    fn edit_candidate(&mut self, config: &str) -> Result<(), String>;
+
+    /// Commits the candidate datastore into `running`, returning the
+    /// transaction id assigned by the daemon.
+    fn commit(&mut self, comment: Option<String>) -> Result<u32, String>;
+
+    /// Discards the candidate datastore, reverting it back to `running`.
+    fn discard_candidate(&mut self) -> Result<(), String>;
+
+    /// Replaces the entire running datastore with `snapshot` (a tree
+    /// previously obtained from [`Client::get`]). Unlike
+    /// [`Client::edit_candidate`], which merges, this removes whatever
+    /// `running` holds that isn't present in `snapshot` — required for a
+    /// confirmed-commit rollback to fully undo a change that added
+    /// config, not just one that edited existing values.
+    fn restore_running(&mut self, snapshot: &str) -> Result<(), String>;
+
+    /// Asks the daemon to persist `source` into `target` (e.g. `running`
+    /// into `startup` for `write memory` / `copy-config running startup`),
+    /// so the change survives a daemon restart.
+    fn copy_config(&mut self, source: Datastore, target: Datastore) -> Result<(), String>;
+}
+
+/// The datastores exposed by `holod`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Datastore {
+    Running,
+    Candidate,
+    Startup,
+}
+
+impl std::str::FromStr for Datastore {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(Datastore::Running),
+            "candidate" => Ok(Datastore::Candidate),
+            "startup" => Ok(Datastore::Startup),
+            _ => Err(format!("unknown datastore '{}'", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Datastore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Datastore::Running => "running",
+            Datastore::Candidate => "candidate",
+            Datastore::Startup => "startup",
+        };
+        write!(f, "{}", name)
+    }
+}
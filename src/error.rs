@@ -0,0 +1,31 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+use std::fmt;
+
+use crate::parser::ParserError;
+use crate::session::EditConfigError;
+use crate::token::CallbackError;
+
+/// Top-level error type returned by [`Cli::enter_command`](crate::Cli::enter_command).
+#[derive(Debug)]
+pub enum Error {
+    Parser(ParserError),
+    EditConfig(EditConfigError),
+    Callback(CallbackError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parser(error) => write!(f, "{}", error),
+            Error::EditConfig(error) => write!(f, "{}", error),
+            Error::Callback(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,267 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+//! Commands that aren't derived from the YANG schema: `show`, `commit`,
+//! `copy-config`, `write memory` and `save startup`.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::client::Datastore;
+use crate::session::{Session, CONFIRMED_COMMIT_DEFAULT_TIMEOUT};
+use crate::token::{Action, Args, CallbackError, Commands, Token};
+
+/// Registers the built-in (non-YANG) commands into `commands`.
+pub fn gen_cmds(commands: &mut Commands) {
+    commands.add_token(Token {
+        name: "show".to_owned(),
+        help: Some("Display a datastore (running by default)".to_owned()),
+        action: Some(Action::Callback(show_configuration)),
+    });
+    commands.add_token(Token {
+        name: "commit".to_owned(),
+        help: Some(
+            "Commit the candidate configuration (optionally with confirmation)".to_owned(),
+        ),
+        action: Some(Action::Callback(commit)),
+    });
+    commands.add_token(Token {
+        name: "copy-config".to_owned(),
+        help: Some("copy-config <source> <target>: copy one datastore into another".to_owned()),
+        action: Some(Action::Callback(copy_config)),
+    });
+    commands.add_token(Token {
+        name: "write".to_owned(),
+        help: Some("write memory: save the running configuration to startup".to_owned()),
+        action: Some(Action::Callback(write_memory)),
+    });
+    commands.add_token(Token {
+        name: "save".to_owned(),
+        help: Some("save startup: save the running configuration to startup".to_owned()),
+        action: Some(Action::Callback(save_startup)),
+    });
+}
+
+/// Parses a trailing `-d <datastore>` pair out of `args`, defaulting to
+/// [`Datastore::Running`] when it's absent.
+fn datastore_arg(args: &Args) -> Result<Datastore, CallbackError> {
+    match args.iter().position(|arg| arg == "-d") {
+        Some(index) => {
+            let name = args.get(index + 1).ok_or_else(|| {
+                CallbackError("-d requires a datastore name".to_owned())
+            })?;
+            Datastore::from_str(name).map_err(CallbackError)
+        }
+        None => Ok(Datastore::Running),
+    }
+}
+
+/// `show [-d {running|startup}]`: dumps the selected datastore through
+/// the session's selected [`Output`](crate::session::Output) back-end.
+/// Both back-ends print the RFC 7951 tree exactly as the daemon returns
+/// it; the text back-end does not pretty-print or indent it.
+fn show_configuration(
+    _commands: &Commands,
+    session: &mut Session,
+    args: Args,
+) -> Result<bool, CallbackError> {
+    let datastore = datastore_arg(&args)?;
+    let tree_json = session
+        .client()
+        .get(datastore, None)
+        .map_err(CallbackError)?;
+
+    // There is no schema-driven text renderer: the raw tree is passed as
+    // both the text and the JSON form.
+    session.output().show(&tree_json, &tree_json);
+
+    Ok(false)
+}
+
+/// `commit`, `commit confirmed [<timeout>] [persist <id>]`, and
+/// `commit confirming persist <id>`.
+///
+/// A bare `commit` confirms a pending confirmed commit if one exists
+/// (matching the rollback behavior NETCONF clients expect), otherwise it
+/// commits normally. If the pending one was armed with a persist-id, a
+/// bare `commit` is rejected; `commit confirming persist <id>` is needed.
+fn commit(
+    _commands: &Commands,
+    session: &mut Session,
+    args: Args,
+) -> Result<bool, CallbackError> {
+    match args.first().map(String::as_str) {
+        Some("confirmed") => {
+            let (timeout, persist_id) = parse_confirmed_args(&args[1..])?;
+            session
+                .candidate_commit_confirmed(timeout, persist_id)
+                .map_err(|error| CallbackError(error.to_string()))?;
+            session.output().status(&format!(
+                "Commit applied; confirm within {}s or it will be rolled back",
+                timeout.as_secs()
+            ));
+            Ok(false)
+        }
+        Some("confirming") => {
+            let persist_id = parse_persist_args(&args[1..])?;
+            session
+                .candidate_commit_confirm(persist_id)
+                .map_err(|error| CallbackError(error.to_string()))?;
+            session.output().status("Confirmed commit accepted");
+            Ok(false)
+        }
+        Some(other) => Err(CallbackError(format!(
+            "unknown 'commit' option '{}'",
+            other
+        ))),
+        None if session.has_pending_confirm() => {
+            session
+                .candidate_commit_confirm(None)
+                .map_err(|error| CallbackError(error.to_string()))?;
+            session.output().status("Confirmed commit accepted");
+            Ok(false)
+        }
+        None => {
+            session
+                .candidate_commit(None)
+                .map_err(|error| CallbackError(error.to_string()))?;
+            Ok(false)
+        }
+    }
+}
+
+/// Parses the `[<timeout>] [persist <id>]` tail of `commit confirmed`,
+/// rejecting anything left over instead of silently ignoring it (a typo'd
+/// or malformed confirmed-commit option must never be mistaken for a
+/// plain, unprotected commit).
+fn parse_confirmed_args(rest: &[String]) -> Result<(Duration, Option<String>), CallbackError> {
+    let mut rest = rest;
+
+    let timeout = match rest.first().and_then(|word| word.parse().ok()) {
+        Some(secs) => {
+            rest = &rest[1..];
+            Duration::from_secs(secs)
+        }
+        None => CONFIRMED_COMMIT_DEFAULT_TIMEOUT,
+    };
+
+    let persist_id = parse_persist_args(rest)?;
+    Ok((timeout, persist_id))
+}
+
+/// Parses an optional `persist <id>` tail, rejecting unrecognized
+/// trailing arguments.
+fn parse_persist_args(rest: &[String]) -> Result<Option<String>, CallbackError> {
+    match rest {
+        [] => Ok(None),
+        [keyword, id] if keyword == "persist" => Ok(Some(id.clone())),
+        [keyword] if keyword == "persist" => {
+            Err(CallbackError("'persist' requires an id".to_owned()))
+        }
+        [unexpected, ..] => Err(CallbackError(format!(
+            "unexpected argument '{}' to 'commit'",
+            unexpected
+        ))),
+    }
+}
+
+/// `copy-config <source> <target>`: persists one datastore into another
+/// (e.g. `copy-config running startup`).
+fn copy_config(
+    _commands: &Commands,
+    session: &mut Session,
+    args: Args,
+) -> Result<bool, CallbackError> {
+    if args.len() != 2 {
+        return Err(CallbackError(
+            "usage: copy-config <source> <target>".to_owned(),
+        ));
+    }
+    let source = Datastore::from_str(&args[0]).map_err(CallbackError)?;
+    let target = Datastore::from_str(&args[1]).map_err(CallbackError)?;
+    session
+        .client()
+        .copy_config(source, target)
+        .map_err(CallbackError)?;
+    Ok(false)
+}
+
+/// `write memory`: shorthand for `copy-config running startup`, so
+/// committed changes survive a daemon restart.
+fn write_memory(
+    _commands: &Commands,
+    session: &mut Session,
+    args: Args,
+) -> Result<bool, CallbackError> {
+    if args.first().map(String::as_str) != Some("memory") {
+        return Err(CallbackError("usage: write memory".to_owned()));
+    }
+    session
+        .client()
+        .copy_config(Datastore::Running, Datastore::Startup)
+        .map_err(CallbackError)?;
+    Ok(false)
+}
+
+/// `save startup`: shorthand for `copy-config running startup`, so
+/// committed changes survive a daemon restart.
+fn save_startup(
+    _commands: &Commands,
+    session: &mut Session,
+    args: Args,
+) -> Result<bool, CallbackError> {
+    if args.first().map(String::as_str) != Some("startup") {
+        return Err(CallbackError("usage: save startup".to_owned()));
+    }
+    session
+        .client()
+        .copy_config(Datastore::Running, Datastore::Startup)
+        .map_err(CallbackError)?;
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirmed_args_default_timeout_and_no_persist() {
+        let (timeout, persist_id) = parse_confirmed_args(&[]).unwrap();
+        assert_eq!(timeout, CONFIRMED_COMMIT_DEFAULT_TIMEOUT);
+        assert_eq!(persist_id, None);
+    }
+
+    #[test]
+    fn confirmed_args_custom_timeout_and_persist() {
+        let args = vec!["120".to_owned(), "persist".to_owned(), "foo".to_owned()];
+        let (timeout, persist_id) = parse_confirmed_args(&args).unwrap();
+        assert_eq!(timeout, Duration::from_secs(120));
+        assert_eq!(persist_id, Some("foo".to_owned()));
+    }
+
+    #[test]
+    fn confirmed_args_persist_without_id_is_rejected() {
+        let args = vec!["persist".to_owned()];
+        assert!(parse_confirmed_args(&args).is_err());
+    }
+
+    #[test]
+    fn confirmed_args_rejects_unexpected_trailing_argument() {
+        let args = vec!["120".to_owned(), "bogus".to_owned()];
+        assert!(parse_confirmed_args(&args).is_err());
+    }
+
+    #[test]
+    fn persist_args_empty_is_none() {
+        assert_eq!(parse_persist_args(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn persist_args_rejects_unknown_keyword() {
+        let args = vec!["bogus".to_owned(), "foo".to_owned()];
+        assert!(parse_persist_args(&args).is_err());
+    }
+}
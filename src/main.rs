@@ -23,7 +23,7 @@ use yang3::context::{Context, ContextFlags};
 use crate::client::grpc::GrpcClient;
 use crate::client::Client;
 use crate::error::Error;
-use crate::session::{CommandMode, Session};
+use crate::session::{CommandMode, Output, Session};
 use crate::terminal::CliPrompt;
 use crate::token::{Action, Commands};
 
@@ -41,13 +41,18 @@ pub struct Cli {
 // ===== impl Cli =====
 
 impl Cli {
-    fn new(use_pager: bool, client: Box<dyn Client>) -> Cli {
+    fn new(
+        use_pager: bool,
+        output: Output,
+        addr: &'static str,
+        client: Box<dyn Client>,
+    ) -> Cli {
         // Generate commands.
         let mut commands = Commands::new();
         commands.gen_cmds();
 
         // Create CLI session.
-        let session = Session::new(use_pager, client);
+        let session = Session::new(use_pager, output, addr, client);
 
         Cli { commands, session }
     }
@@ -91,31 +96,81 @@ impl Cli {
 
 // ===== global functions =====
 
-fn read_config_file(mut cli: Cli, path: &str) {
+// Reads a configuration script from `path` (or stdin, if `path` is "-")
+// and applies it. In abort-on-error mode (the default) any failed line
+// discards the candidate and exits non-zero instead of committing
+// whatever happened to parse; `--best-effort` restores the old behavior
+// of skipping bad lines and committing the rest.
+fn read_config_file(mut cli: Cli, path: &str, abort_on_error: bool) {
     // Enter configuration mode.
     let mode = CommandMode::Configure { nodes: vec![] };
     cli.session.mode_set(mode);
 
-    // Read file from the filesystem.
-    let file = match std::fs::read_to_string(path) {
-        Ok(file) => file,
-        Err(error) => {
-            eprintln!("% failed to read file path: {}", error);
-            return;
+    // Read the configuration script from the filesystem or from stdin.
+    let file = if path == "-" {
+        use std::io::Read;
+        let mut buf = String::new();
+        if let Err(error) = std::io::stdin().read_to_string(&mut buf) {
+            cli.session
+                .output()
+                .error(&format!("failed to read stdin: {}", error));
+            std::process::exit(1);
+        }
+        buf
+    } else {
+        match std::fs::read_to_string(path) {
+            Ok(file) => file,
+            Err(error) => {
+                cli.session
+                    .output()
+                    .error(&format!("failed to read file path: {}", error));
+                std::process::exit(1);
+            }
         }
     };
 
     // Read configuration.
     for line in file.lines() {
         if let Err(error) = cli.enter_command(line) {
-            eprintln!("% {}", error);
+            cli.session.output().error(&error);
+            if abort_on_error {
+                if let Err(error) = cli.session.candidate_discard() {
+                    cli.session.output().error(&error);
+                }
+                cli.session.output().error(
+                    &"aborting: configuration load failed, candidate discarded".to_string(),
+                );
+                std::process::exit(1);
+            }
         }
     }
 
     // Commit configuration.
     let comment = Some(format!("Configuration read from {}", path));
-    if let Err(err) = cli.session.candidate_commit(comment) {
-        eprintln!("% {}", err);
+    if let Err(error) = cli.session.candidate_commit(comment) {
+        cli.session.output().error(&error);
+        std::process::exit(1);
+    }
+}
+
+// Enables `feature` on every loaded module that declares it. Unlike
+// libyang's own feature lookup, this doesn't stop at the first match,
+// since more than one loaded module can expose a feature of the same
+// name; it reports an error and exits if none of them do, rather than
+// silently ignoring a typo'd or unsupported `-e`.
+fn enable_feature(ctx: &mut Context, feature: &str) {
+    let mut found = false;
+    for module in ctx.modules(false) {
+        if module.feature_enable(feature).is_ok() {
+            found = true;
+        }
+    }
+    if !found {
+        eprintln!(
+            "Failed to enable YANG feature '{}': not declared by any loaded module",
+            feature
+        );
+        std::process::exit(1);
     }
 }
 
@@ -127,7 +182,19 @@ fn main() {
             Arg::with_name("file")
                 .long("file")
                 .value_name("path")
-                .help("Read configuration file"),
+                .help("Read configuration file ('-' for stdin)"),
+        )
+        .arg(
+            Arg::with_name("abort-on-error")
+                .long("abort-on-error")
+                .conflicts_with("best-effort")
+                .help("Discard the candidate and exit non-zero on the first bad line in --file (default)"),
+        )
+        .arg(
+            Arg::with_name("best-effort")
+                .long("best-effort")
+                .conflicts_with("abort-on-error")
+                .help("Skip bad lines in --file instead of aborting the whole load"),
         )
         .arg(
             Arg::with_name("no-colors")
@@ -155,6 +222,27 @@ fn main() {
                 .help("Holo daemon IPv4/6 address: http://IP:Port")
                 .multiple(false),
         )
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .help("Render show output and command errors as JSON instead of text (startup errors stay plain text)"),
+        )
+        .arg(
+            Arg::with_name("search-dir")
+                .short("s")
+                .value_name("dir")
+                .help("Append an extra YANG module search directory")
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("feature")
+                .short("e")
+                .value_name("feature")
+                .help("Enable an optional YANG feature (e.g. candidate, rollback-on-error)")
+                .multiple(true)
+                .number_of_values(1),
+        )
         .get_matches();
 
     // Connect to the daemon.
@@ -190,18 +278,45 @@ fn main() {
     // Set YANG search directory.
     yang_ctx.set_searchdir(YANG_MODULES_DIR).unwrap();
 
+    // Append any extra search directories requested with -s, so operators
+    // can point at local module copies without rebuilding.
+    if let Some(dirs) = matches.values_of("search-dir") {
+        for dir in dirs {
+            if let Err(error) = yang_ctx.set_searchdir(dir) {
+                eprintln!("Failed to add YANG search directory ({}): {}", dir, error);
+                std::process::exit(1);
+            }
+        }
+    }
+
     // Load YANG modules.
     client.load_modules(grpc_addr, &mut yang_ctx);
+
+    // Enable any optional features requested with -e, so operators can
+    // exercise them without rebuilding.
+    if let Some(features) = matches.values_of("feature") {
+        for feature in features {
+            enable_feature(&mut yang_ctx, feature);
+        }
+    }
+
     YANG_CTX.set(Arc::new(yang_ctx)).unwrap();
 
     // Initialize CLI master structure.
     let use_pager = matches.values_of("command").is_none()
         && !matches.is_present("no-pager");
-    let mut cli = Cli::new(use_pager, Box::new(client));
+    let output = if matches.is_present("json") {
+        Output::Json
+    } else {
+        Output::Text
+    };
+    let mut cli = Cli::new(use_pager, output, grpc_addr, Box::new(client));
 
     // Read configuration file.
     if let Some(path) = matches.value_of("file") {
-        read_config_file(cli, path);
+        let abort_on_error =
+            matches.is_present("abort-on-error") || !matches.is_present("best-effort");
+        read_config_file(cli, path, abort_on_error);
         return;
     }
 
@@ -212,7 +327,7 @@ fn main() {
     if let Some(commands) = matches.values_of("command") {
         for command in commands {
             if let Err(error) = cli.enter_command(command) {
-                println!("% {}", error)
+                cli.session.output().error(&error);
             }
         }
         return;
@@ -236,7 +351,7 @@ fn main() {
                 }
             }
             Err(error) => {
-                println!("% {}", error)
+                cli.session.output().error(&error);
             }
         };
 
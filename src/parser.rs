@@ -0,0 +1,162 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+use std::fmt;
+
+use crate::session::Session;
+use crate::token::{Args, Commands, TokenId};
+
+/// Error produced while normalizing or matching an input line against the
+/// command tree.
+#[derive(Debug)]
+pub enum ParserError {
+    UnknownCommand,
+    AmbiguousCommand,
+    MissingArgument(String),
+    UnbalancedQuotes,
+}
+
+impl fmt::Display for ParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParserError::UnknownCommand => write!(f, "unknown command"),
+            ParserError::AmbiguousCommand => write!(f, "ambiguous command"),
+            ParserError::MissingArgument(name) => {
+                write!(f, "missing argument: {}", name)
+            }
+            ParserError::UnbalancedQuotes => {
+                write!(f, "unbalanced quotes in command line")
+            }
+        }
+    }
+}
+
+/// A command once its token has been resolved and its arguments collected.
+pub struct ParsedCommand {
+    pub token_id: TokenId,
+    pub negate: bool,
+    pub args: Args,
+}
+
+/// Strips comments and surrounding whitespace from a raw input line,
+/// returning `None` for lines that carry no command (blank lines and
+/// comments).
+pub fn normalize_input_line(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('!') || line.starts_with('#') {
+        return None;
+    }
+    Some(line.to_string())
+}
+
+/// Splits `line` into words the way a POSIX shell would: single/double
+/// quoted and backslash-escaped segments collapse into one argument, so a
+/// leaf value like a description string or an interface alias can carry
+/// embedded spaces (`description "uplink to core"`). Returns
+/// [`ParserError::UnbalancedQuotes`] if a quote is left open.
+fn tokenize(line: &str) -> Result<Vec<String>, ParserError> {
+    shell_words::split(line).map_err(|_| ParserError::UnbalancedQuotes)
+}
+
+/// Tokenizes `line` and matches the resulting words against `commands`,
+/// collecting the leaf token and its arguments.
+pub fn parse_command(
+    _session: &mut Session,
+    commands: &Commands,
+    line: &str,
+) -> Result<ParsedCommand, ParserError> {
+    let negate = line.starts_with("no ") || line == "no";
+    let line = line.strip_prefix("no ").unwrap_or(line);
+    let words = tokenize(line)?;
+
+    if words.is_empty() {
+        return Err(ParserError::UnknownCommand);
+    }
+
+    let word_refs: Vec<&str> = words.iter().map(String::as_str).collect();
+    let token_id = commands
+        .find(&word_refs)
+        .ok_or(ParserError::UnknownCommand)?;
+    let args = words[1..].to_vec();
+
+    Ok(ParsedCommand {
+        token_id,
+        negate,
+        args,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{Client, Datastore};
+    use crate::session::Output;
+    use crate::token::Token;
+    use yang3::context::Context;
+
+    /// A [`Client`] that never talks to holod, just enough to construct a
+    /// [`Session`] for parser tests.
+    struct NullClient;
+
+    impl Client for NullClient {
+        fn load_modules(&mut self, _addr: &str, _ctx: &mut Context) {}
+
+        fn get(&mut self, _datastore: Datastore, _xpath: Option<&str>) -> Result<String, String> {
+            Err("not implemented".to_owned())
+        }
+
+        fn edit_candidate(&mut self, _config: &str) -> Result<(), String> {
+            Err("not implemented".to_owned())
+        }
+
+        fn commit(&mut self, _comment: Option<String>) -> Result<u32, String> {
+            Err("not implemented".to_owned())
+        }
+
+        fn discard_candidate(&mut self) -> Result<(), String> {
+            Err("not implemented".to_owned())
+        }
+
+        fn restore_running(&mut self, _snapshot: &str) -> Result<(), String> {
+            Err("not implemented".to_owned())
+        }
+
+        fn copy_config(&mut self, _source: Datastore, _target: Datastore) -> Result<(), String> {
+            Err("not implemented".to_owned())
+        }
+    }
+
+    fn test_session() -> Session {
+        Session::new(false, Output::Text, "http://[::1]:50051", Box::new(NullClient))
+    }
+
+    // Regression test for the historical bug where `commit confirmed 120
+    // persist foo` resolved against the *last* word instead of the first
+    // (`Commands::find` matching `words.last()`) and always threw away the
+    // arguments (`parser::parse_command` hardcoding `args: Vec::new()`),
+    // leaving the confirmed-commit feature unreachable despite its own
+    // command being registered.
+    #[test]
+    fn commit_confirmed_resolves_token_and_keeps_args() {
+        let mut commands = Commands::new();
+        let commit_id = commands.add_token(Token {
+            name: "commit".to_owned(),
+            help: None,
+            action: None,
+        });
+        let mut session = test_session();
+
+        let pcmd = parse_command(&mut session, &commands, "commit confirmed 120 persist foo")
+            .expect("valid command line");
+
+        assert_eq!(pcmd.token_id, commit_id);
+        assert!(!pcmd.negate);
+        assert_eq!(
+            pcmd.args,
+            vec!["confirmed", "120", "persist", "foo"]
+        );
+    }
+}
@@ -0,0 +1,419 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+use std::fmt;
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::client::grpc::GrpcClient;
+use crate::client::{Client, Datastore};
+use crate::token::Args;
+
+/// Default rollback window for `commit confirmed` when no explicit
+/// timeout is given.
+pub const CONFIRMED_COMMIT_DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Error returned while editing the candidate configuration.
+#[derive(Debug)]
+pub struct EditConfigError(pub String);
+
+impl fmt::Display for EditConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Where in the command tree the session currently is.
+pub enum CommandMode {
+    Operational,
+    Configure { nodes: Vec<String> },
+}
+
+/// Selects how command output and errors get rendered: the default
+/// pretty-printed text for interactive use, or RFC 7951 JSON for scripting
+/// (`holo-cli --json show ... | jq`).
+pub enum Output {
+    Text,
+    Json,
+}
+
+impl Output {
+    /// Emits the result of a `show`-style command: `text` on the text
+    /// back-end, or `tree_json` (an RFC 7951-encoded YANG data tree) on
+    /// the JSON back-end.
+    pub fn show(&self, text: &str, tree_json: &str) {
+        match self {
+            Output::Text => println!("{}", text),
+            Output::Json => println!("{}", tree_json),
+        }
+    }
+
+    /// Emits an error: `% error` on stderr for text, `{"error": "..."}` on
+    /// stderr for JSON.
+    pub fn error(&self, error: &dyn fmt::Display) {
+        match self {
+            Output::Text => eprintln!("% {}", error),
+            Output::Json => {
+                eprintln!("{{\"error\": \"{}\"}}", escape_json_string(&error.to_string()))
+            }
+        }
+    }
+
+    /// Emits a one-line status message for a command that doesn't return a
+    /// data tree (e.g. `commit confirmed`'s rollback-window notice): plain
+    /// text on the text back-end, `{"status": "..."}` on stdout for JSON.
+    pub fn status(&self, message: &str) {
+        match self {
+            Output::Text => println!("{}", message),
+            Output::Json => println!("{{\"status\": \"{}\"}}", escape_json_string(message)),
+        }
+    }
+}
+
+/// Escapes `value` for use inside a JSON string literal per RFC 8259:
+/// backslashes and quotes, plus control characters (as `\n`/`\r`/`\t` or a
+/// `\u00XX` escape). Used instead of a full JSON serializer since `Output`
+/// only ever wraps a single string field.
+fn escape_json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Tells a pending rollback timer why it's being stopped before its
+/// timeout elapses, so it can skip the rollback.
+enum TimerStop {
+    /// The commit was confirmed; keep the change.
+    Confirmed,
+    /// A second `commit confirmed` replaced this one; its own timer takes
+    /// over protecting the (now newer) change.
+    Replaced,
+}
+
+/// A `commit confirmed` that hasn't been confirmed (or timed out) yet.
+struct PendingConfirm {
+    persist_id: Option<String>,
+    // `None` once `stop` has run. Sending a `TimerStop` wakes the timer
+    // thread immediately instead of waiting out its full timeout; simply
+    // dropping the sender without sending one (see `Drop`) disconnects
+    // the channel, which the timer treats the same as a timeout.
+    stop_tx: Option<mpsc::Sender<TimerStop>>,
+    timer: Option<JoinHandle<()>>,
+}
+
+impl PendingConfirm {
+    /// Stops the rollback timer, optionally telling it why so it skips
+    /// the rollback, then waits for it to finish (which is near-instant
+    /// unless it's actually rolling back).
+    fn stop(&mut self, reason: Option<TimerStop>) {
+        if let Some(tx) = self.stop_tx.take() {
+            if let Some(reason) = reason {
+                let _ = tx.send(reason);
+            }
+        }
+        if let Some(timer) = self.timer.take() {
+            let _ = timer.join();
+        }
+    }
+}
+
+impl Drop for PendingConfirm {
+    fn drop(&mut self) {
+        // No reason given: the channel disconnects and the timer rolls
+        // back, same as a timeout. This is what happens when the CLI
+        // exits with a confirmed commit still pending.
+        self.stop(None);
+    }
+}
+
+/// Holds everything tied to a single CLI invocation: the transport to
+/// holod, the current command mode, and how output gets rendered.
+pub struct Session {
+    client: Box<dyn Client>,
+    // Holod's gRPC address, kept around so the rollback timer can reconnect
+    // on its own thread after the session (and `client`) may be gone.
+    addr: &'static str,
+    mode: CommandMode,
+    use_pager: bool,
+    hostname: String,
+    output: Output,
+    pending_confirm: Option<PendingConfirm>,
+}
+
+impl Session {
+    pub fn new(
+        use_pager: bool,
+        output: Output,
+        addr: &'static str,
+        client: Box<dyn Client>,
+    ) -> Session {
+        Session {
+            client,
+            addr,
+            mode: CommandMode::Operational,
+            use_pager,
+            hostname: String::from("holo"),
+            output,
+            pending_confirm: None,
+        }
+    }
+
+    pub fn output(&self) -> &Output {
+        &self.output
+    }
+
+    pub fn client(&mut self) -> &mut dyn Client {
+        self.client.as_mut()
+    }
+
+    pub fn mode(&self) -> &CommandMode {
+        &self.mode
+    }
+
+    pub fn mode_set(&mut self, mode: CommandMode) {
+        self.mode = mode;
+    }
+
+    pub fn use_pager(&self) -> bool {
+        self.use_pager
+    }
+
+    pub fn prompt(&self) -> String {
+        match &self.mode {
+            CommandMode::Operational => format!("{}> ", self.hostname),
+            CommandMode::Configure { .. } => format!("{}(config)# ", self.hostname),
+        }
+    }
+
+    /// Refreshes `self.hostname` from the running configuration so the
+    /// prompt tracks the daemon's configured hostname.
+    pub fn update_hostname(&mut self) {
+        if let Ok(tree) = self
+            .client
+            .get(crate::client::Datastore::Running, Some("/ietf-system:system/hostname"))
+        {
+            if let Some(hostname) = scalar_leaf_value(&tree) {
+                self.hostname = hostname;
+            }
+        }
+    }
+
+    /// Applies `args` against the configuration node identified by
+    /// `path`, negating it if `negate` is set.
+    pub fn edit_candidate(
+        &mut self,
+        negate: bool,
+        path: &str,
+        args: Args,
+    ) -> Result<(), EditConfigError> {
+        let xml = crate::token_xml::args_to_xml(path, &args, negate);
+        self.client
+            .edit_candidate(&xml)
+            .map_err(EditConfigError)
+    }
+
+    /// Commits the candidate configuration into the running datastore.
+    pub fn candidate_commit(
+        &mut self,
+        comment: Option<String>,
+    ) -> Result<u32, EditConfigError> {
+        self.client.commit(comment).map_err(EditConfigError)
+    }
+
+    /// Discards the candidate configuration, reverting it back to the
+    /// running datastore.
+    pub fn candidate_discard(&mut self) -> Result<(), EditConfigError> {
+        self.client.discard_candidate().map_err(EditConfigError)
+    }
+
+    /// Commits the candidate configuration, but snapshots `running` first
+    /// and arms a `timeout` timer that automatically rolls back to the
+    /// snapshot unless a matching confirming commit arrives first.
+    ///
+    /// Only one confirmed commit may be pending at a time. A second call
+    /// while one is already pending replaces its timeout and snapshot if
+    /// `persist_id` matches the pending one; otherwise it's rejected.
+    pub fn candidate_commit_confirmed(
+        &mut self,
+        timeout: Duration,
+        persist_id: Option<String>,
+    ) -> Result<u32, EditConfigError> {
+        if let Some(pending) = &self.pending_confirm {
+            if pending.persist_id != persist_id {
+                return Err(EditConfigError(
+                    "a confirmed commit is already pending with a different persist-id"
+                        .to_owned(),
+                ));
+            }
+        }
+
+        let snapshot = self
+            .client
+            .get(Datastore::Running, None)
+            .map_err(EditConfigError)?;
+        let txn_id = self.client.commit(Some("confirmed commit".to_owned())).map_err(EditConfigError)?;
+
+        // Replacing a pending confirmed commit stops its timer without
+        // rolling back (the change it was protecting is superseded, not
+        // reverted); the new one below takes over with a fresh snapshot
+        // and timeout.
+        if let Some(mut old) = self.pending_confirm.take() {
+            old.stop(Some(TimerStop::Replaced));
+        }
+
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let addr = self.addr;
+        let timer = thread::spawn(move || match stop_rx.recv_timeout(timeout) {
+            Ok(TimerStop::Confirmed) | Ok(TimerStop::Replaced) => {}
+            Err(_) => rollback(addr, &snapshot),
+        });
+
+        self.pending_confirm = Some(PendingConfirm {
+            persist_id,
+            stop_tx: Some(stop_tx),
+            timer: Some(timer),
+        });
+
+        Ok(txn_id)
+    }
+
+    /// Whether a `commit confirmed` is currently awaiting confirmation.
+    pub fn has_pending_confirm(&self) -> bool {
+        self.pending_confirm.is_some()
+    }
+
+    /// Confirms the pending `commit confirmed`, discarding its rollback
+    /// snapshot and timer. Returns an error if there's nothing pending or
+    /// if `persist_id` doesn't match the one the pending commit was armed
+    /// with.
+    pub fn candidate_commit_confirm(
+        &mut self,
+        persist_id: Option<String>,
+    ) -> Result<(), EditConfigError> {
+        match &self.pending_confirm {
+            Some(pending) if pending.persist_id == persist_id => {
+                let mut pending = self.pending_confirm.take().unwrap();
+                pending.stop(Some(TimerStop::Confirmed));
+                Ok(())
+            }
+            Some(_) => Err(EditConfigError(
+                "persist-id does not match the pending confirmed commit".to_owned(),
+            )),
+            None => Err(EditConfigError(
+                "no confirmed commit is pending".to_owned(),
+            )),
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        // If the CLI exits while a confirmed commit is still pending,
+        // dropping it here disconnects the timer's channel, which it
+        // treats the same as a timeout: it rolls back to the pre-commit
+        // snapshot rather than leaving the change live with nobody left
+        // to confirm it.
+        self.pending_confirm.take();
+    }
+}
+
+/// Restores `snapshot` (an RFC 7951-encoded running-config tree taken
+/// before a confirmed commit) as the daemon's running datastore. Runs on
+/// its own connection since it may fire from the rollback timer thread or
+/// from `Session::drop`, after the original client may already be gone.
+///
+/// Goes through [`Client::restore_running`] rather than
+/// `edit_candidate` + `commit`: the latter merges, so it can't remove
+/// nodes the confirmed commit added, which would leave the rollback
+/// incomplete in exactly the lockout scenario confirmed commit exists to
+/// protect against.
+fn rollback(addr: &'static str, snapshot: &str) {
+    let mut client = match GrpcClient::connect(addr) {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("% confirmed commit rollback failed to connect to holod: {}", error);
+            return;
+        }
+    };
+    if let Err(error) = client.restore_running(snapshot) {
+        eprintln!("% confirmed commit rollback failed: {}", error);
+    }
+}
+
+/// Pulls the scalar value out of a single-leaf RFC 7951 query result,
+/// e.g. `{"ietf-system:hostname":"myrouter"}` -> `Some("myrouter")`.
+/// Returns `None` for anything that isn't exactly one string-valued key.
+///
+/// Module-qualified keys (`"ietf-system:hostname"`) contain a colon of
+/// their own, so this can't just split on the first `:` in the object —
+/// that hits the one inside the key instead of the one separating it
+/// from the value. Instead it walks past the quoted key explicitly and
+/// only then looks for the `:` that follows it.
+fn scalar_leaf_value(tree: &str) -> Option<String> {
+    let tree = tree.trim().trim_start_matches('{').trim_end_matches('}').trim();
+    let rest = tree.strip_prefix('"')?;
+    let key_end = rest.find('"')?;
+    let rest = rest[key_end + 1..].trim_start();
+    let rest = rest.strip_prefix(':')?.trim_start();
+    let value = rest.strip_prefix('"')?;
+    let value_end = value.rfind('"')?;
+    let value = &value[..value_end];
+    if value.is_empty() || value.contains('"') {
+        None
+    } else {
+        Some(value.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalar_leaf_value_parses_module_qualified_key() {
+        let tree = r#"{"ietf-system:hostname":"myrouter"}"#;
+        assert_eq!(scalar_leaf_value(tree), Some("myrouter".to_owned()));
+    }
+
+    #[test]
+    fn scalar_leaf_value_rejects_empty_value() {
+        let tree = r#"{"ietf-system:hostname":""}"#;
+        assert_eq!(scalar_leaf_value(tree), None);
+    }
+
+    #[test]
+    fn scalar_leaf_value_rejects_non_object() {
+        assert_eq!(scalar_leaf_value("not json"), None);
+    }
+
+    #[test]
+    fn escape_json_string_escapes_quotes_and_backslashes() {
+        assert_eq!(
+            escape_json_string(r#"a "quoted" \ value"#),
+            r#"a \"quoted\" \\ value"#
+        );
+    }
+
+    #[test]
+    fn escape_json_string_escapes_control_characters() {
+        assert_eq!(
+            escape_json_string("line1\nline2\ttab\r"),
+            "line1\\nline2\\ttab\\r"
+        );
+        assert_eq!(escape_json_string("\u{0001}"), "\\u0001");
+    }
+}
@@ -0,0 +1,56 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+use std::sync::{Arc, Mutex};
+
+use reedline::{Prompt, PromptEditMode, PromptHistorySearch, Reedline};
+
+use crate::Cli;
+
+/// Renders the current mode prompt (`holo> `, `holo(config)# `, ...).
+pub struct CliPrompt {
+    prompt: String,
+}
+
+impl CliPrompt {
+    pub fn new(prompt: String) -> CliPrompt {
+        CliPrompt { prompt }
+    }
+
+    pub fn update(&mut self, prompt: String) {
+        self.prompt = prompt;
+    }
+}
+
+impl Prompt for CliPrompt {
+    fn render_prompt_left(&self) -> std::borrow::Cow<str> {
+        std::borrow::Cow::Borrowed(&self.prompt)
+    }
+
+    fn render_prompt_right(&self) -> std::borrow::Cow<str> {
+        std::borrow::Cow::Borrowed("")
+    }
+
+    fn render_prompt_indicator(&self, _mode: PromptEditMode) -> std::borrow::Cow<str> {
+        std::borrow::Cow::Borrowed("")
+    }
+
+    fn render_prompt_multiline_indicator(&self) -> std::borrow::Cow<str> {
+        std::borrow::Cow::Borrowed("... ")
+    }
+
+    fn render_prompt_history_search_indicator(
+        &self,
+        _history_search: PromptHistorySearch,
+    ) -> std::borrow::Cow<str> {
+        std::borrow::Cow::Borrowed("(search)> ")
+    }
+}
+
+/// Builds the `reedline` editor used for the interactive main loop.
+pub fn reedline_init(_cli: Arc<Mutex<Cli>>, use_ansi_coloring: bool) -> Reedline {
+    Reedline::create().use_ansi_coloring(use_ansi_coloring)
+}
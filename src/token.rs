@@ -0,0 +1,81 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+use std::fmt;
+
+use crate::session::Session;
+
+pub type TokenId = usize;
+/// The words following a matched command name, in order (e.g. `commit
+/// confirmed 120 persist foo` matches the `commit` token with
+/// `["confirmed", "120", "persist", "foo"]`).
+pub type Args = Vec<String>;
+
+/// Error returned by a command [`Callback`].
+#[derive(Debug)]
+pub struct CallbackError(pub String);
+
+impl fmt::Display for CallbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A command callback, invoked once its token has been matched and its
+/// arguments parsed.
+pub type Callback =
+    fn(&Commands, &mut Session, Args) -> Result<bool, CallbackError>;
+
+/// What happens once a token is fully matched.
+pub enum Action {
+    /// Edit the YANG configuration node identified by `path`.
+    ConfigEdit(String),
+    /// Run an arbitrary callback (`show` commands, `commit`, etc).
+    Callback(Callback),
+}
+
+/// A single word in the command tree (e.g. `show`, `interface`, `<name>`).
+pub struct Token {
+    pub name: String,
+    pub help: Option<String>,
+    pub action: Option<Action>,
+}
+
+/// The full command tree, generated from the loaded YANG modules plus the
+/// built-in (non-YANG) commands registered in [`crate::internal_commands`].
+pub struct Commands {
+    tokens: Vec<Token>,
+}
+
+impl Commands {
+    pub fn new() -> Commands {
+        Commands { tokens: Vec::new() }
+    }
+
+    /// Populates the command tree from the YANG schema and the internal
+    /// commands table.
+    pub fn gen_cmds(&mut self) {
+        crate::token_yang::gen_cmds(self);
+        crate::internal_commands::gen_cmds(self);
+    }
+
+    pub fn get_token(&self, token_id: TokenId) -> &Token {
+        &self.tokens[token_id]
+    }
+
+    pub fn add_token(&mut self, token: Token) -> TokenId {
+        self.tokens.push(token);
+        self.tokens.len() - 1
+    }
+
+    /// Resolves the command name (`words[0]`) to the token it matches, if
+    /// any; the rest of `words` becomes that token's [`Args`]. Matching is
+    /// by exact name only; prefixes and abbreviations are not matched.
+    pub fn find(&self, words: &[&str]) -> Option<TokenId> {
+        let name = words.first()?;
+        self.tokens.iter().position(|token| token.name == *name)
+    }
+}
@@ -0,0 +1,73 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+//! Helpers for turning parsed command arguments into the XML-encoded
+//! edit-config fragments that [`crate::client::Client::edit_candidate`]
+//! sends to holod.
+
+use crate::token::Args;
+
+/// Escapes the characters XML reserves for markup (`&`, `<`, `>`, `"`) so
+/// a leaf value can't inject sibling or child elements into the
+/// edit-config payload it's spliced into.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders `args` as a minimal `<path>value</path>`-style XML fragment
+/// rooted at `path`. `args` holds the leaf value as its only element for
+/// simple leafs, or several positional values for list keys.
+///
+/// When `negate` is set, the element carries `nc:operation="delete"` per
+/// RFC 6241 `edit-config`, so holod removes the node instead of
+/// merging/creating it.
+pub fn args_to_xml(path: &str, args: &Args, negate: bool) -> String {
+    let value = args.iter().map(|arg| escape(arg)).collect::<Vec<_>>().join(" ");
+    if negate {
+        format!(
+            "<{0} xmlns:nc=\"urn:ietf:params:xml:ns:netconf:base:1.0\" nc:operation=\"delete\">{1}</{0}>",
+            path, value
+        )
+    } else {
+        format!("<{0}>{1}</{0}>", path, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_xml_reserved_characters() {
+        let args: Args = vec!["<evil attr=\"x\">&amp;</evil>".to_owned()];
+        let xml = args_to_xml("description", &args, false);
+        assert_eq!(
+            xml,
+            "<description>&lt;evil attr=&quot;x&quot;&gt;&amp;amp;&lt;/evil&gt;</description>"
+        );
+    }
+
+    #[test]
+    fn plain_element_has_no_operation_attribute() {
+        let args: Args = vec!["eth0".to_owned()];
+        let xml = args_to_xml("name", &args, false);
+        assert_eq!(xml, "<name>eth0</name>");
+    }
+
+    #[test]
+    fn negate_adds_delete_operation_attribute() {
+        let args: Args = vec!["eth0".to_owned()];
+        let xml = args_to_xml("name", &args, true);
+        assert_eq!(
+            xml,
+            "<name xmlns:nc=\"urn:ietf:params:xml:ns:netconf:base:1.0\" nc:operation=\"delete\">eth0</name>"
+        );
+    }
+}
